@@ -0,0 +1,181 @@
+// Read/write control surface for power limits and clock locks.
+//
+// Unlike the metrics getters, these calls configure the device and therefore
+// need a group scoped to a single GPU (DCGM's config API is group-oriented),
+// plus elevated privileges on the host engine.
+use crate::dcgm_types::{
+    DcgmConfig, DcgmConfigClockSet, DcgmConfigPowerLimit, DCGM_CONFIG_POWER_CAP_INDIVIDUAL,
+    DCGM_CONFIG_TARGET_STATE, DCGM_GROUP_EMPTY, DCGM_INT32_BLANK,
+};
+use crate::{DcgmError, DcgmHandle, Result};
+use std::ffi::CString;
+use std::ptr;
+
+fn map_config_result(result: i32, context: &str) -> Result<()> {
+    if result == 0 {
+        Ok(())
+    } else if result == -29 {
+        // DCGM_ST_REQUIRES_ROOT
+        Err(DcgmError::PermissionDenied(context.to_string()))
+    } else {
+        Err(DcgmError::ApiError(result, context.to_string()))
+    }
+}
+
+impl DcgmHandle {
+    // dcgmConfigSet/dcgmConfigGet operate on a group, so wrap `device_id` in a
+    // throwaway single-GPU group for the duration of `f`, cleaning it up
+    // afterwards regardless of the outcome.
+    fn with_single_gpu_group<F, T>(&self, device_id: u32, f: F) -> Result<T>
+    where
+        F: FnOnce(u64) -> Result<T>,
+    {
+        let pid = std::process::id();
+        let group_name = CString::new(format!("CtrlGroup{}", pid)).unwrap();
+        let mut group_id: u64 = 0;
+
+        let result = unsafe {
+            (self.api.group_create)(
+                self.handle,
+                DCGM_GROUP_EMPTY,
+                group_name.as_ptr(),
+                &mut group_id,
+            )
+        };
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmGroupCreate failed for control group".to_string(),
+            ));
+        }
+
+        let result = unsafe { (self.api.group_add_device)(self.handle, group_id, device_id) };
+        if result != 0 {
+            self.destroy_group(group_id);
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmGroupAddDevice failed for control group".to_string(),
+            ));
+        }
+
+        let outcome = f(group_id);
+
+        self.destroy_group(group_id);
+
+        outcome
+    }
+
+    fn destroy_group(&self, group_id: u64) {
+        unsafe {
+            let _ = (self.api.group_destroy)(self.handle, group_id);
+        }
+    }
+
+    /// Set the enforced power limit, in Watts, for `device_id`.
+    pub fn set_power_limit(&self, device_id: u32, watts: u32) -> Result<()> {
+        self.with_single_gpu_group(device_id, |group_id| {
+            let mut config = DcgmConfig {
+                gpu_id: device_id,
+                power_limit: DcgmConfigPowerLimit {
+                    target_type: DCGM_CONFIG_POWER_CAP_INDIVIDUAL,
+                    val: watts as i32,
+                },
+                ..DcgmConfig::default()
+            };
+
+            let result = unsafe {
+                (self.api.config_set)(self.handle, group_id, &mut config, ptr::null_mut())
+            };
+            map_config_result(result, "dcgmConfigSet failed for power limit")
+        })
+    }
+
+    /// Get the currently targeted (enforced-on-reset) power limit, in Watts,
+    /// for `device_id`, so callers can verify their `set_power_limit` stuck.
+    pub fn get_power_limit_target(&self, device_id: u32) -> Result<Option<i64>> {
+        self.with_single_gpu_group(device_id, |group_id| {
+            let config = self.config_get(group_id, "power limit")?;
+            if config.power_limit.val >= DCGM_INT32_BLANK {
+                Ok(None)
+            } else {
+                Ok(Some(config.power_limit.val as i64))
+            }
+        })
+    }
+
+    /// Lock `device_id`'s memory and SM clocks to the given values, in MHz.
+    pub fn set_locked_clocks(&self, device_id: u32, mem_mhz: u32, sm_mhz: u32) -> Result<()> {
+        self.with_single_gpu_group(device_id, |group_id| {
+            let mut config = DcgmConfig {
+                gpu_id: device_id,
+                clock_set: DcgmConfigClockSet {
+                    mem_clock: mem_mhz as i32,
+                    sm_clock: sm_mhz as i32,
+                },
+                ..DcgmConfig::default()
+            };
+
+            let result = unsafe {
+                (self.api.config_set)(self.handle, group_id, &mut config, ptr::null_mut())
+            };
+            map_config_result(result, "dcgmConfigSet failed for locked clocks")
+        })
+    }
+
+    /// Get the currently targeted memory/SM clock lock, in MHz, for
+    /// `device_id`, so callers can verify their `set_locked_clocks` stuck.
+    pub fn get_locked_clocks_target(&self, device_id: u32) -> Result<(Option<i64>, Option<i64>)> {
+        self.with_single_gpu_group(device_id, |group_id| {
+            let config = self.config_get(group_id, "locked clocks")?;
+            let mem_clock = if config.clock_set.mem_clock >= DCGM_INT32_BLANK {
+                None
+            } else {
+                Some(config.clock_set.mem_clock as i64)
+            };
+            let sm_clock = if config.clock_set.sm_clock >= DCGM_INT32_BLANK {
+                None
+            } else {
+                Some(config.clock_set.sm_clock as i64)
+            };
+            Ok((mem_clock, sm_clock))
+        })
+    }
+
+    /// Drop any clock lock on `device_id`, letting it clock freely again.
+    pub fn reset_clocks(&self, device_id: u32) -> Result<()> {
+        self.with_single_gpu_group(device_id, |group_id| {
+            let mut config = DcgmConfig {
+                gpu_id: device_id,
+                ..DcgmConfig::default()
+            };
+
+            let result = unsafe {
+                (self.api.config_set)(self.handle, group_id, &mut config, ptr::null_mut())
+            };
+            map_config_result(result, "dcgmConfigSet failed while resetting clocks")
+        })
+    }
+
+    fn config_get(&self, group_id: u64, context: &str) -> Result<DcgmConfig> {
+        let mut configs = [DcgmConfig::default(); 1];
+        let result = unsafe {
+            (self.api.config_get)(
+                self.handle,
+                group_id,
+                DCGM_CONFIG_TARGET_STATE,
+                configs.len() as i32,
+                configs.as_mut_ptr(),
+                ptr::null_mut(),
+            )
+        };
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                format!("dcgmConfigGet failed for {}", context),
+            ));
+        }
+
+        Ok(configs[0])
+    }
+}
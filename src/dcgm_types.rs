@@ -15,12 +15,26 @@ pub const DCGM_FI_DEV_POWER_USAGE: u16 = 155;
 pub const DCGM_FI_DEV_TOTAL_ENERGY_CONSUMPTION: u16 = 156;
 pub const DCGM_FI_DEV_GPU_TEMP: u16 = 150;
 pub const DCGM_FI_DEV_ENFORCED_POWER_LIMIT: u16 = 164;
+pub const DCGM_FI_DEV_MIN_POWER_LIMIT: u16 = 161;
+pub const DCGM_FI_DEV_MAX_POWER_LIMIT: u16 = 162;
+pub const DCGM_FI_DEV_DEFAULT_POWER_LIMIT: u16 = 163;
+pub const DCGM_FI_DEV_FAN_SPEED: u16 = 139;
 pub const DCGM_FI_DEV_GPU_MAX_OP_TEMP: u16 = 152;
 pub const DCGM_FI_DEV_POWER_VIOLATION: u16 = 240;
 pub const DCGM_FI_DEV_THERMAL_VIOLATION: u16 = 241;
 pub const DCGM_FI_DEV_CLOCKS_EVENT_REASONS: u16 = 112;
 
 pub const DCGM_FI_PROF_SM_ACTIVE: u16 = 1002;
+pub const DCGM_FI_PROF_SM_OCCUPANCY: u16 = 1003;
+pub const DCGM_FI_PROF_PIPE_TENSOR_ACTIVE: u16 = 1004;
+pub const DCGM_FI_PROF_DRAM_ACTIVE: u16 = 1005;
+pub const DCGM_FI_PROF_PIPE_FP64_ACTIVE: u16 = 1006;
+pub const DCGM_FI_PROF_PIPE_FP32_ACTIVE: u16 = 1007;
+pub const DCGM_FI_PROF_PIPE_FP16_ACTIVE: u16 = 1008;
+pub const DCGM_FI_PROF_PCIE_TX_BYTES: u16 = 1009;
+pub const DCGM_FI_PROF_PCIE_RX_BYTES: u16 = 1010;
+pub const DCGM_FI_PROF_NVLINK_TX_BYTES: u16 = 1011;
+pub const DCGM_FI_PROF_NVLINK_RX_BYTES: u16 = 1012;
 
 // Device Metadata
 pub const DCGM_FI_DEV_NAME: u16 = 50;
@@ -56,6 +70,65 @@ pub const DCGM_CLOCKS_EVENT_REASON_HW_THERMAL: u64 = 0x0000000000000040;
 pub const DCGM_CLOCKS_EVENT_REASON_HW_POWER_BRAKE: u64 = 0x0000000000000080;
 pub const DCGM_CLOCKS_EVENT_REASON_DISPLAY_CLOCKS: u64 = 0x0000000000000100;
 
+// Process accounting
+// DCGM classifies each PID it accounts for as a compute or graphics
+// context; anything else (or a driver that doesn't report it) comes back
+// as unknown.
+pub const DCGM_PROCESS_TYPE_UNKNOWN: i32 = 0;
+pub const DCGM_PROCESS_TYPE_COMPUTE: i32 = 1;
+pub const DCGM_PROCESS_TYPE_GRAPHICS: i32 = 2;
+
+// Max number of processes dcgmGetAllProcesses will hand back in one call
+pub const DCGM_MAX_PID_INFO_PROCESSES: usize = 128;
+
+// Group management
+pub const DCGM_GROUP_EMPTY: i32 = 2;
+
+// dcgmConfigSet/dcgmConfigGet target types
+pub const DCGM_CONFIG_TARGET_STATE: i32 = 0;
+pub const DCGM_CONFIG_CURRENT_STATE: i32 = 1;
+
+// Power limit types accepted by dcgmConfig_t::powerLimit
+pub const DCGM_CONFIG_POWER_CAP_INDIVIDUAL: i32 = 1;
+
+// Status codes returned in DcgmFieldValue::status
+pub const DCGM_ST_OK: i32 = 0;
+pub const DCGM_ST_NOT_SUPPORTED: i32 = -17;
+pub const DCGM_ST_REQUIRES_ROOT: i32 = -29;
+
+// Health systems accepted by dcgmHealthSet, OR'd together to pick what
+// dcgmHealthCheck watches and reports incidents for.
+pub const DCGM_HEALTH_WATCH_PCIE: u32 = 0x1;
+pub const DCGM_HEALTH_WATCH_NVLINK: u32 = 0x2;
+pub const DCGM_HEALTH_WATCH_PMU: u32 = 0x4;
+pub const DCGM_HEALTH_WATCH_MCU: u32 = 0x8;
+pub const DCGM_HEALTH_WATCH_MEM: u32 = 0x10;
+pub const DCGM_HEALTH_WATCH_SM: u32 = 0x20;
+pub const DCGM_HEALTH_WATCH_INFOROM: u32 = 0x40;
+pub const DCGM_HEALTH_WATCH_THERMAL: u32 = 0x80;
+pub const DCGM_HEALTH_WATCH_POWER: u32 = 0x100;
+pub const DCGM_HEALTH_WATCH_DRIVER: u32 = 0x200;
+pub const DCGM_HEALTH_WATCH_NVSWITCH_NONFATAL: u32 = 0x400;
+pub const DCGM_HEALTH_WATCH_NVSWITCH_FATAL: u32 = 0x800;
+pub const DCGM_HEALTH_WATCH_ALL: u32 = 0xffffffff;
+
+// dcgmIncidentInfo_t::health values returned by dcgmHealthCheck
+pub const DCGM_HEALTH_RESULT_PASS: i32 = 0;
+pub const DCGM_HEALTH_RESULT_WARN: i32 = 1;
+pub const DCGM_HEALTH_RESULT_FAIL: i32 = 2;
+
+// Max number of incidents dcgmHealthCheck will hand back in one call
+pub const DCGM_HEALTH_MAX_INCIDENTS: usize = 64;
+
+// dcgmPolicyCondition_t bits accepted by dcgmPolicySet/dcgmPolicyRegister
+pub const DCGM_POLICY_COND_DBE: i32 = 0x1;
+pub const DCGM_POLICY_COND_PCI: i32 = 0x2;
+pub const DCGM_POLICY_COND_MAX_PAGES_RETIRED: i32 = 0x4;
+pub const DCGM_POLICY_COND_THERMAL: i32 = 0x8;
+pub const DCGM_POLICY_COND_POWER: i32 = 0x10;
+pub const DCGM_POLICY_COND_NVLINK: i32 = 0x20;
+pub const DCGM_POLICY_COND_XID: i32 = 0x40;
+
 // Used to check if a value is blank
 #[inline]
 pub fn is_int64_blank(val: i64) -> bool {
@@ -67,6 +140,40 @@ pub fn is_fp64_blank(val: f64) -> bool {
     val >= DCGM_FP64_BLANK
 }
 
+/// Decode a `DcgmFieldValue` down to an `f64` regardless of its underlying
+/// int/double representation, returning `None` for blank (unsupported) samples.
+#[inline]
+pub fn decode_numeric_value(field_value: &DcgmFieldValue) -> Option<f64> {
+    match field_value.field_type as i8 {
+        DCGM_FT_INT64 => {
+            let value = unsafe { field_value.value.i64 };
+            if is_int64_blank(value) {
+                None
+            } else {
+                Some(value as f64)
+            }
+        }
+        DCGM_FT_DOUBLE => {
+            let value = unsafe { field_value.value.dbl };
+            if is_fp64_blank(value) {
+                None
+            } else {
+                Some(value)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// One (entity group, entity) pair identifying a single monitored object —
+/// e.g. `{ DCGM_FE_GPU, gpu_id }` — passed to `dcgmEntitiesGetLatestValues`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmGroupEntityPair {
+    pub entity_group_id: u32,
+    pub entity_id: u32,
+}
+
 #[repr(C)]
 pub struct DcgmFieldValue {
     pub version: u32,
@@ -103,3 +210,155 @@ impl Default for DcgmFieldValue {
         }
     }
 }
+
+/// One entry returned by `dcgmGetAllProcesses`, describing a single PID's
+/// accounted GPU usage since it started running on the device.
+#[repr(C)]
+pub struct DcgmPidInfo {
+    pub version: u32,
+    pub pid: u32,
+    pub process_type: i32, // DCGM_PROCESS_TYPE_*
+    pub used_fb_bytes: u64,
+    pub sm_util: u32,
+    pub start_time: i64,
+    pub end_time: i64, // 0 while the process is still running
+}
+
+/// The power cap DCGM should enforce on a GPU, and how it should enforce it.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmConfigPowerLimit {
+    pub target_type: i32, // DCGM_CONFIG_POWER_CAP_*
+    pub val: i32,         // in Watts
+}
+
+/// Locked memory/SM clocks DCGM should enforce on a GPU.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmConfigClockSet {
+    pub mem_clock: i32, // in MHz
+    pub sm_clock: i32,  // in MHz
+}
+
+/// Mirrors the subset of `dcgmConfig_t` this crate reads and writes, used by
+/// `dcgmConfigSet`/`dcgmConfigGet`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmConfig {
+    pub version: u32,
+    pub gpu_id: u32,
+    pub power_limit: DcgmConfigPowerLimit,
+    pub clock_set: DcgmConfigClockSet,
+}
+
+impl Default for DcgmConfig {
+    fn default() -> Self {
+        DcgmConfig {
+            version: 1,
+            gpu_id: 0,
+            power_limit: DcgmConfigPowerLimit {
+                target_type: DCGM_INT32_BLANK,
+                val: DCGM_INT32_BLANK,
+            },
+            clock_set: DcgmConfigClockSet {
+                mem_clock: DCGM_INT32_BLANK,
+                sm_clock: DCGM_INT32_BLANK,
+            },
+        }
+    }
+}
+
+impl Default for DcgmPidInfo {
+    fn default() -> Self {
+        DcgmPidInfo {
+            version: 1,
+            pid: 0,
+            process_type: DCGM_PROCESS_TYPE_UNKNOWN,
+            used_fb_bytes: DCGM_INT64_BLANK as u64,
+            sm_util: DCGM_INT32_BLANK as u32,
+            start_time: 0,
+            end_time: 0,
+        }
+    }
+}
+
+/// One incident entry returned by `dcgmHealthCheck`, describing a single
+/// GPU's health problem in one of the watched systems.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmHealthIncident {
+    pub system: u32, // DCGM_HEALTH_WATCH_* bit that raised this incident
+    pub health: u32, // DCGM_HEALTH_RESULT_*
+    pub error_code: i32,
+    pub error_msg: [i8; 256], // DCGM_MAX_STR_LENGTH
+    pub entity_group_id: u32,
+    pub entity_id: u32,
+}
+
+impl Default for DcgmHealthIncident {
+    fn default() -> Self {
+        DcgmHealthIncident {
+            system: 0,
+            health: DCGM_HEALTH_RESULT_PASS as u32,
+            error_code: 0,
+            error_msg: [0; 256],
+            entity_group_id: 0,
+            entity_id: 0,
+        }
+    }
+}
+
+/// Mirrors the subset of `dcgmHealthResponse_t` this crate reads, populated
+/// by `dcgmHealthCheck`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmHealthResponse {
+    pub version: u32,
+    pub overall_health: u32, // DCGM_HEALTH_RESULT_*
+    pub incident_count: u32,
+    pub incidents: [DcgmHealthIncident; DCGM_HEALTH_MAX_INCIDENTS],
+}
+
+impl Default for DcgmHealthResponse {
+    fn default() -> Self {
+        DcgmHealthResponse {
+            version: 1,
+            overall_health: DCGM_HEALTH_RESULT_PASS as u32,
+            incident_count: 0,
+            incidents: [DcgmHealthIncident::default(); DCGM_HEALTH_MAX_INCIDENTS],
+        }
+    }
+}
+
+/// Mirrors the subset of `dcgmPolicy_t` this crate writes: a single numeric
+/// threshold per condition, rather than the full per-condition parameter
+/// union `dcgmPolicySet` actually accepts.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmPolicy {
+    pub version: u32,
+    pub condition: i32, // OR of DCGM_POLICY_COND_* bits this threshold applies to
+    pub threshold: u32,
+}
+
+impl Default for DcgmPolicy {
+    fn default() -> Self {
+        DcgmPolicy {
+            version: 1,
+            condition: 0,
+            threshold: 0,
+        }
+    }
+}
+
+/// Mirrors the subset of `dcgmPolicyCallbackResponse_t` this crate reads,
+/// handed to the callback registered via `dcgmPolicyRegister`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct DcgmPolicyCallbackResponse {
+    pub version: u32,
+    pub condition: i32, // the single DCGM_POLICY_COND_* bit that fired
+    pub gpu_id: u32,
+    pub error_code: i32,
+    pub timestamp: i64,
+}
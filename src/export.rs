@@ -0,0 +1,355 @@
+// Prometheus/OpenMetrics text-exposition formatting for the metrics structs,
+// so callers can drop straight into a scrape pipeline instead of hand-rolling
+// formatting off the public fields.
+//
+// Multi-GPU callers are the whole point, so these are free functions over a
+// slice rather than a per-struct method: Prometheus' text format requires
+// each metric name's `# HELP`/`# TYPE` block to appear exactly once, which a
+// per-device `to_prometheus()` can't guarantee once its output is
+// concatenated across devices.
+use crate::metrics::{GpuMetrics, GpuProcessMetrics, GpuProfilingMetrics, ProcessType};
+
+fn format_labels(device_id: u32, extra_labels: &[(&str, &str)]) -> String {
+    let mut labels = vec![format!("gpu=\"{}\"", device_id)];
+    labels.extend(extra_labels.iter().map(|(k, v)| format!("{}=\"{}\"", k, v)));
+    labels.join(",")
+}
+
+// Writes `name`'s `# HELP`/`# TYPE` block the first time `get` returns
+// `Some` for an item in `items`, then one gauge sample line per such item.
+// Emits nothing at all if no item has a value for this metric.
+fn write_metric<T, V: std::fmt::Display>(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    items: &[T],
+    labels: impl Fn(&T) -> String,
+    get: impl Fn(&T) -> Option<V>,
+) {
+    let mut wrote_metadata = false;
+
+    for item in items {
+        if let Some(value) = get(item) {
+            if !wrote_metadata {
+                out.push_str(&format!("# HELP {} {}\n", name, help));
+                out.push_str(&format!("# TYPE {} gauge\n", name));
+                wrote_metadata = true;
+            }
+            out.push_str(&format!("{}{{{}}} {}\n", name, labels(item), value));
+        }
+    }
+}
+
+/// Render `metrics` as Prometheus/OpenMetrics text exposition, tagging every
+/// series with `gpu="<device_id>"` plus any `extra_labels` the caller
+/// supplies (e.g. `uuid`, `hostname`), with each metric name's `# HELP`/
+/// `# TYPE` block written exactly once across every device in `metrics`.
+pub fn gpu_metrics_to_prometheus(metrics: &[GpuMetrics], extra_labels: &[(&str, &str)]) -> String {
+    let mut out = String::new();
+    let labels = |m: &GpuMetrics| format_labels(m.device_id, extra_labels);
+
+    write_metric(
+        &mut out,
+        "dcgm_power_usage_watts",
+        "Current power draw in watts",
+        metrics,
+        labels,
+        |m| m.power_usage,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_energy_consumption_millijoules",
+        "Total energy consumption in millijoules",
+        metrics,
+        labels,
+        |m| m.energy_consumption,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_enforced_power_limit_watts",
+        "Currently enforced power limit in watts",
+        metrics,
+        labels,
+        |m| m.enforced_power_limit,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_min_power_limit_watts",
+        "Minimum power limit supported in watts",
+        metrics,
+        labels,
+        |m| m.min_power_limit,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_max_power_limit_watts",
+        "Maximum power limit supported in watts",
+        metrics,
+        labels,
+        |m| m.max_power_limit,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_default_power_limit_watts",
+        "Default power limit in watts",
+        metrics,
+        labels,
+        |m| m.default_power_limit,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_power_violation_microseconds",
+        "Cumulative time spent power-throttled in microseconds",
+        metrics,
+        labels,
+        |m| m.power_violation_time,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_gpu_temp_celsius",
+        "Current GPU temperature in degrees Celsius",
+        metrics,
+        labels,
+        |m| m.gpu_temp,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_max_gpu_temp_celsius",
+        "Maximum operating temperature in degrees Celsius",
+        metrics,
+        labels,
+        |m| m.max_gpu_temp,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_thermal_violation_microseconds",
+        "Cumulative time spent thermal-throttled in microseconds",
+        metrics,
+        labels,
+        |m| m.thermal_violation_time,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_fan_speed_percent",
+        "Fan speed as a percentage of max",
+        metrics,
+        labels,
+        |m| m.fan_speed,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_fb_total_megabytes",
+        "Total framebuffer memory in megabytes",
+        metrics,
+        labels,
+        |m| m.fb_total,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_fb_free_megabytes",
+        "Free framebuffer memory in megabytes",
+        metrics,
+        labels,
+        |m| m.fb_free,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_fb_used_megabytes",
+        "Used framebuffer memory in megabytes",
+        metrics,
+        labels,
+        |m| m.fb_used,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_gpu_util_percent",
+        "GPU utilization as a percentage",
+        metrics,
+        labels,
+        |m| m.gpu_util,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_sm_clock_mhz",
+        "SM clock in MHz",
+        metrics,
+        labels,
+        |m| m.sm_clock,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_mem_clock_mhz",
+        "Memory clock in MHz",
+        metrics,
+        labels,
+        |m| m.mem_clock,
+    );
+
+    let mut wrote_metadata = false;
+    for metric in metrics {
+        let Some(reasons) = &metric.throttle_reasons else {
+            continue;
+        };
+
+        for reason in reasons {
+            if !wrote_metadata {
+                out.push_str("# HELP dcgm_clock_throttle_reason Active clock throttling reasons\n");
+                out.push_str("# TYPE dcgm_clock_throttle_reason gauge\n");
+                wrote_metadata = true;
+            }
+            out.push_str(&format!(
+                "dcgm_clock_throttle_reason{{{},reason=\"{}\"}} 1\n",
+                labels(metric),
+                reason
+            ));
+        }
+    }
+
+    out
+}
+
+/// Render `metrics` as Prometheus/OpenMetrics text exposition, with each
+/// metric name's `# HELP`/`# TYPE` block written exactly once across every
+/// device in `metrics`.
+pub fn gpu_profiling_metrics_to_prometheus(
+    metrics: &[GpuProfilingMetrics],
+    extra_labels: &[(&str, &str)],
+) -> String {
+    let mut out = String::new();
+    let labels = |m: &GpuProfilingMetrics| format_labels(m.device_id, extra_labels);
+
+    write_metric(
+        &mut out,
+        "dcgm_sm_active_ratio",
+        "Ratio of cycles an SM had at least one warp assigned",
+        metrics,
+        labels,
+        |m| m.sm_active,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_sm_occupancy_ratio",
+        "Ratio of warps resident on an SM versus the maximum possible",
+        metrics,
+        labels,
+        |m| m.sm_occupancy,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_tensor_active_ratio",
+        "Ratio of cycles the tensor cores were active",
+        metrics,
+        labels,
+        |m| m.tensor_active,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_fp64_active_ratio",
+        "Ratio of cycles the FP64 pipe was active",
+        metrics,
+        labels,
+        |m| m.fp64_active,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_fp32_active_ratio",
+        "Ratio of cycles the FP32 pipe was active",
+        metrics,
+        labels,
+        |m| m.fp32_active,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_fp16_active_ratio",
+        "Ratio of cycles the FP16 pipe was active",
+        metrics,
+        labels,
+        |m| m.fp16_active,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_dram_active_ratio",
+        "Ratio of cycles the memory interface was active",
+        metrics,
+        labels,
+        |m| m.dram_active,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_pcie_tx_bytes_per_second",
+        "PCIe transmit throughput in bytes/sec",
+        metrics,
+        labels,
+        |m| m.pcie_tx_bytes,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_pcie_rx_bytes_per_second",
+        "PCIe receive throughput in bytes/sec",
+        metrics,
+        labels,
+        |m| m.pcie_rx_bytes,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_nvlink_tx_bytes_per_second",
+        "NVLink transmit throughput in bytes/sec",
+        metrics,
+        labels,
+        |m| m.nvlink_tx_bytes,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_nvlink_rx_bytes_per_second",
+        "NVLink receive throughput in bytes/sec",
+        metrics,
+        labels,
+        |m| m.nvlink_rx_bytes,
+    );
+
+    out
+}
+
+fn process_type_label(process_type: ProcessType) -> &'static str {
+    match process_type {
+        ProcessType::Compute => "compute",
+        ProcessType::Graphics => "graphics",
+        ProcessType::Unknown => "unknown",
+    }
+}
+
+/// Render `metrics` as Prometheus/OpenMetrics text exposition, tagged with
+/// `pid`/`type` in addition to the usual `gpu` label, with each metric
+/// name's `# HELP`/`# TYPE` block written exactly once across every process
+/// in `metrics`.
+pub fn gpu_process_metrics_to_prometheus(
+    metrics: &[GpuProcessMetrics],
+    extra_labels: &[(&str, &str)],
+) -> String {
+    let mut out = String::new();
+    let labels = |m: &GpuProcessMetrics| {
+        let mut labels = format_labels(m.device_id, extra_labels);
+        labels.push_str(&format!(",pid=\"{}\"", m.pid));
+        labels.push_str(&format!(",type=\"{}\"", process_type_label(m.process_type)));
+        labels
+    };
+
+    write_metric(
+        &mut out,
+        "dcgm_process_fb_used_bytes",
+        "Framebuffer memory used by this process in bytes",
+        metrics,
+        labels,
+        |m| m.used_fb_bytes,
+    );
+    write_metric(
+        &mut out,
+        "dcgm_process_sm_util_percent",
+        "SM utilization attributed to this process as a percentage",
+        metrics,
+        labels,
+        |m| m.sm_util,
+    );
+
+    out
+}
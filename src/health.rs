@@ -0,0 +1,372 @@
+// Event-driven wrapper around DCGM's health-check APIs: `HealthWatcher::new`
+// enables the requested health watches, then `start` spins up a background
+// thread that periodically runs `dcgmHealthCheck`, diffs the incidents it
+// reports against what was already seen, and sends a `HealthEvent` for each
+// new one. `check_now` runs the same poll-and-diff step synchronously,
+// without requiring the background thread to be running.
+use crate::dcgm_types::{
+    DcgmHealthIncident, DcgmHealthResponse, DcgmPolicy, DcgmPolicyCallbackResponse,
+    DCGM_HEALTH_RESULT_FAIL, DCGM_HEALTH_RESULT_WARN,
+};
+use crate::{DcgmError, DcgmHandle, Result};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Severity DCGM assigned to a health-check incident.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthSeverity {
+    Warning,
+    Failure,
+}
+
+/// One health incident newly observed for a single GPU.
+#[derive(Debug, Clone)]
+pub struct HealthEvent {
+    pub gpu_id: u32,
+    pub system: u32, // the DCGM_HEALTH_WATCH_* bit that raised this incident
+    pub severity: HealthSeverity,
+    pub detail: String,
+}
+
+// De-duplicates incidents across polls so a still-ongoing problem doesn't
+// refire an event every tick.
+type IncidentKey = (u32, u32, i32); // (entity_id, system, error_code)
+
+/// Background health-check poller for a set of `DCGM_HEALTH_WATCH_*` systems.
+/// Enables the corresponding watches for its lifetime and clears them again
+/// on drop; new incidents are delivered over `events`.
+///
+/// Only one `HealthWatcher` may be live per `DcgmHandle` at a time:
+/// `dcgmHealthSet` replaces (rather than ORs into) the group's watched-systems
+/// bitmask, and `Drop` unconditionally clears it, so two concurrently-live
+/// watchers for different systems would stomp each other's watched bits and
+/// either one dropping would disable health watching entirely — the same
+/// single-callback-per-handle caveat [`PolicyWatcher`] documents for itself.
+pub struct HealthWatcher {
+    handle: Arc<DcgmHandle>,
+    systems: u32,
+    seen: Arc<Mutex<HashSet<IncidentKey>>>,
+    sender: Sender<HealthEvent>,
+    pub events: Receiver<HealthEvent>,
+    stop_flag: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HealthWatcher {
+    /// Enable health watching for `systems` (an OR of `DCGM_HEALTH_WATCH_*`
+    /// bits) on all GPUs. Call [`HealthWatcher::start`] to begin polling.
+    pub fn new(handle: Arc<DcgmHandle>, systems: u32) -> Result<Self> {
+        handle.set_health_watch(systems)?;
+        let (sender, events) = mpsc::channel();
+
+        Ok(HealthWatcher {
+            handle,
+            systems,
+            seen: Arc::new(Mutex::new(HashSet::new())),
+            sender,
+            events,
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        })
+    }
+
+    /// Start polling every `interval`, sending a [`HealthEvent`] over `events`
+    /// for each newly observed incident. No-op if already running.
+    pub fn start(&mut self, interval: Duration) {
+        if self.thread.is_some() {
+            return;
+        }
+
+        self.stop_flag.store(false, Ordering::SeqCst);
+        let handle = Arc::clone(&self.handle);
+        let seen = Arc::clone(&self.seen);
+        let sender = self.sender.clone();
+        let stop_flag = Arc::clone(&self.stop_flag);
+        let systems = self.systems;
+
+        self.thread = Some(thread::spawn(move || {
+            while !stop_flag.load(Ordering::SeqCst) {
+                if let Ok(events) = poll_once(&handle, systems, &seen) {
+                    for event in events {
+                        if sender.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+
+                thread::sleep(interval);
+            }
+        }));
+    }
+
+    /// Stop the background thread and join it, if running.
+    pub fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    /// Run `dcgmHealthCheck` once, synchronously, and return every incident
+    /// not already reported by a previous poll (background or otherwise).
+    pub fn check_now(&self) -> Result<Vec<HealthEvent>> {
+        poll_once(&self.handle, self.systems, &self.seen)
+    }
+}
+
+impl Drop for HealthWatcher {
+    fn drop(&mut self) {
+        self.stop();
+        let _ = self.handle.set_health_watch(0);
+    }
+}
+
+/// A policy-violation condition crossed on a GPU, delivered to the closure
+/// registered via [`PolicyWatcher::on_violation`].
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyViolation {
+    pub gpu_id: u32,
+    pub condition: i32, // the DCGM_POLICY_COND_* bit that fired
+    pub error_code: i32,
+    pub timestamp: i64,
+}
+
+type PolicyCallback = Box<dyn Fn(PolicyViolation) + Send>;
+
+// dcgmPolicyRegister's callback is a bare `fn(*mut dcgmPolicyCallbackResponse_t)`
+// with no user-data pointer, so there's no way for `policy_trampoline` to
+// recover which `PolicyWatcher` it belongs to. DCGM itself only ever drives
+// one registered callback per process, so a single global slot is enough —
+// registering a new watcher's closure simply replaces whatever was there.
+// Each entry is tagged with the id of the `PolicyWatcher` that installed it,
+// so a watcher's `Drop` only clears the slot if it still owns it (otherwise
+// it would silently kill whichever later watcher's callback replaced it).
+static POLICY_CALLBACK: Mutex<Option<(u64, PolicyCallback)>> = Mutex::new(None);
+static NEXT_POLICY_WATCHER_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+unsafe extern "C" fn policy_trampoline(response: *mut DcgmPolicyCallbackResponse) {
+    if response.is_null() {
+        return;
+    }
+    let response = unsafe { &*response };
+
+    let violation = PolicyViolation {
+        gpu_id: response.gpu_id,
+        condition: response.condition,
+        error_code: response.error_code,
+        timestamp: response.timestamp,
+    };
+
+    if let Ok(slot) = POLICY_CALLBACK.lock() {
+        if let Some((_, callback)) = slot.as_ref() {
+            callback(violation);
+        }
+    }
+}
+
+/// Wraps `dcgmPolicySet`/`dcgmPolicyRegister`, letting a caller register a
+/// closure that fires whenever a GPU crosses one of `condition`'s
+/// thresholds. Unlike [`HealthWatcher`]'s poll-and-diff model, delivery here
+/// is a genuine DCGM-driven callback, so there's no background thread to
+/// start or stop.
+///
+/// Only one `PolicyWatcher`'s callback can be the live `POLICY_CALLBACK` at
+/// a time (DCGM itself only drives one registered callback per process);
+/// registering a new watcher's closure replaces whatever callback was
+/// previously active, the same single-watcher-per-handle caveat
+/// [`HealthWatcher`] has for its watched systems.
+pub struct PolicyWatcher {
+    handle: Arc<DcgmHandle>,
+    condition: i32,
+    id: u64,
+    registered: bool,
+}
+
+impl PolicyWatcher {
+    /// Set `threshold` for `condition` (an OR of `DCGM_POLICY_COND_*` bits)
+    /// on all GPUs. Call [`PolicyWatcher::on_violation`] to start receiving
+    /// callbacks.
+    pub fn new(handle: Arc<DcgmHandle>, condition: i32, threshold: u32) -> Result<Self> {
+        handle.set_policy(condition, threshold)?;
+
+        Ok(PolicyWatcher {
+            handle,
+            condition,
+            id: NEXT_POLICY_WATCHER_ID.fetch_add(1, Ordering::SeqCst),
+            registered: false,
+        })
+    }
+
+    /// Register `callback` to fire whenever a GPU crosses this watcher's
+    /// condition. Only one callback may be registered per process at a time
+    /// — see the note on `POLICY_CALLBACK` — so registering a second
+    /// [`PolicyWatcher`] replaces the first one's callback rather than
+    /// adding to it.
+    pub fn on_violation<F>(&mut self, callback: F) -> Result<()>
+    where
+        F: Fn(PolicyViolation) + Send + 'static,
+    {
+        if let Ok(mut slot) = POLICY_CALLBACK.lock() {
+            *slot = Some((self.id, Box::new(callback)));
+        }
+
+        self.handle.register_policy_callback(self.condition)?;
+        self.registered = true;
+        Ok(())
+    }
+}
+
+impl Drop for PolicyWatcher {
+    fn drop(&mut self) {
+        if self.registered {
+            let _ = self.handle.unregister_policy(self.condition);
+
+            if let Ok(mut slot) = POLICY_CALLBACK.lock() {
+                // Only clear the slot if it's still ours — a later
+                // PolicyWatcher may have replaced it already.
+                if matches!(slot.as_ref(), Some((id, _)) if *id == self.id) {
+                    *slot = None;
+                }
+            }
+        }
+    }
+}
+
+fn poll_once(
+    handle: &DcgmHandle,
+    systems: u32,
+    seen: &Mutex<HashSet<IncidentKey>>,
+) -> Result<Vec<HealthEvent>> {
+    let response = handle.run_health_check()?;
+
+    let count = (response.incident_count as usize).min(response.incidents.len());
+    let mut events = Vec::new();
+
+    let mut seen = match seen.lock() {
+        Ok(seen) => seen,
+        Err(_) => return Ok(events),
+    };
+
+    for incident in &response.incidents[..count] {
+        if incident.system & systems == 0 {
+            continue;
+        }
+
+        let severity = match incident.health as i32 {
+            DCGM_HEALTH_RESULT_FAIL => HealthSeverity::Failure,
+            DCGM_HEALTH_RESULT_WARN => HealthSeverity::Warning,
+            _ => continue, // DCGM_HEALTH_RESULT_PASS, nothing to report
+        };
+
+        let key = (incident.entity_id, incident.system, incident.error_code);
+        if !seen.insert(key) {
+            continue;
+        }
+
+        events.push(HealthEvent {
+            gpu_id: incident.entity_id,
+            system: incident.system,
+            severity,
+            detail: incident_message(incident),
+        });
+    }
+
+    Ok(events)
+}
+
+fn incident_message(incident: &DcgmHealthIncident) -> String {
+    let bytes: Vec<u8> = incident
+        .error_msg
+        .iter()
+        .take_while(|&&b| b != 0)
+        .map(|&b| b as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+impl DcgmHandle {
+    fn set_health_watch(&self, systems: u32) -> Result<()> {
+        let result = unsafe { (self.api.health_set)(self.handle, 0x7fffffff, systems) }; // DCGM_GROUP_ALL_GPUS
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmHealthSet failed".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn run_health_check(&self) -> Result<DcgmHealthResponse> {
+        let mut response = DcgmHealthResponse::default();
+        let result = unsafe { (self.api.health_check)(self.handle, 0x7fffffff, &mut response) }; // DCGM_GROUP_ALL_GPUS
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmHealthCheck failed".to_string(),
+            ));
+        }
+
+        Ok(response)
+    }
+
+    fn set_policy(&self, condition: i32, threshold: u32) -> Result<()> {
+        let mut policy = DcgmPolicy {
+            condition,
+            threshold,
+            ..DcgmPolicy::default()
+        };
+
+        let result =
+            unsafe { (self.api.policy_set)(self.handle, 0x7fffffff, &mut policy, std::ptr::null_mut()) }; // DCGM_GROUP_ALL_GPUS
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmPolicySet failed".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn register_policy_callback(&self, condition: i32) -> Result<()> {
+        let result = unsafe {
+            (self.api.policy_register)(
+                self.handle,
+                0x7fffffff, // DCGM_GROUP_ALL_GPUS
+                condition,
+                policy_trampoline,
+                policy_trampoline,
+            )
+        };
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmPolicyRegister failed".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn unregister_policy(&self, condition: i32) -> Result<()> {
+        let result = unsafe { (self.api.policy_unregister)(self.handle, 0x7fffffff, condition) }; // DCGM_GROUP_ALL_GPUS
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmPolicyUnregister failed".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,25 @@
-use libloading::{Library, Symbol};
-use std::{ffi::CString, ptr, time::Duration};
+use libloading::Library;
+use std::{
+    collections::HashMap,
+    ffi::CString,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
 use thiserror::Error;
 
+use crate::dcgm_types::{
+    DcgmConfig, DcgmFieldValue, DcgmGroupEntityPair, DcgmHealthResponse, DcgmPidInfo, DcgmPolicy,
+    DcgmPolicyCallbackResponse, DCGM_FE_GPU, DCGM_FV_FLAG_LIVE_DATA, DCGM_ST_OK,
+};
+
+pub mod control;
 pub mod dcgm_types;
+pub mod export;
+pub mod health;
 pub mod metrics;
+pub mod subscribe;
 
 #[derive(Error, Debug)]
 pub enum DcgmError {
@@ -27,37 +43,163 @@ pub enum DcgmError {
 
     #[error("Requires root: {0}")]
     RequiresRoot(String),
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
 }
 
 pub type Result<T> = std::result::Result<T, DcgmError>;
 
+/// Identifies one entry in a `DcgmHandle`'s field-group watch registry.
+pub type WatchId = u64;
+
+struct WatchEntry {
+    field_group_id: u64,
+    entity_group_id: u64,
+    update_freq_usec: i64,
+    max_keep_age_sec: f64,
+    max_keep_samples: i32,
+}
+
+/// Retention settings a [`WatchId`] was created with, returned by
+/// [`DcgmHandle::watch_info`]/[`WatchHandle::info`].
+#[derive(Debug, Clone, Copy)]
+pub struct WatchInfo {
+    pub update_freq_usec: i64,
+    pub max_keep_age_sec: f64,
+    pub max_keep_samples: i32,
+}
+
+/// An RAII guard for a field-group watch created by [`DcgmHandle::watch_fields`].
+/// Dropping it unwatches and destroys only this watch's field group.
+pub struct WatchHandle {
+    id: WatchId,
+    handle: Arc<DcgmHandle>,
+}
+
+impl WatchHandle {
+    pub fn id(&self) -> WatchId {
+        self.id
+    }
+
+    /// Retention settings this watch was created with, or `None` if it has
+    /// already been torn down.
+    pub fn info(&self) -> Option<WatchInfo> {
+        self.handle.watch_info(self.id)
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.handle.teardown_watch(self.id);
+    }
+}
+
+// Every DCGM entry point this crate calls, resolved once from the loaded
+// `Library` and kept around as plain function pointers instead of looking a
+// symbol up through `lib.get(...)` on every call. The pointers only stay
+// valid for as long as the `Library` they came from is alive, which is why
+// `DcgmApi` only ever appears alongside the owning `Library` in `DcgmHandle`.
+struct DcgmApi {
+    init: unsafe extern "C" fn() -> i32,
+    start_embedded: unsafe extern "C" fn(i32, *mut u64) -> i32,
+    connect: unsafe extern "C" fn(*const i8, *mut u64) -> i32,
+    update_all_fields: unsafe extern "C" fn(u64, i32) -> i32,
+    stop_embedded: unsafe extern "C" fn(u64) -> i32,
+    shutdown: unsafe extern "C" fn() -> i32,
+    get_all_devices: unsafe extern "C" fn(u64, *mut u32, *mut i32) -> i32,
+    field_group_create: unsafe extern "C" fn(u64, i32, *mut u16, *const i8, *mut u64) -> i32,
+    field_group_destroy: unsafe extern "C" fn(u64, u64) -> i32,
+    watch_fields: unsafe extern "C" fn(u64, u64, u64, i64, f64, i32) -> i32,
+    unwatch_fields: unsafe extern "C" fn(u64, u64, u64) -> i32,
+    watch_pid_fields: unsafe extern "C" fn(u64, u64, i64, f64, i32) -> i32,
+    get_all_processes: unsafe extern "C" fn(u64, u32, *mut DcgmPidInfo, u32, *mut u32) -> i32,
+    group_create: unsafe extern "C" fn(u64, i32, *const i8, *mut u64) -> i32,
+    group_add_device: unsafe extern "C" fn(u64, u64, u32) -> i32,
+    group_destroy: unsafe extern "C" fn(u64, u64) -> i32,
+    config_set: unsafe extern "C" fn(u64, u64, *mut DcgmConfig, *mut u8) -> i32,
+    config_get: unsafe extern "C" fn(u64, u64, i32, i32, *mut DcgmConfig, *mut u8) -> i32,
+    health_set: unsafe extern "C" fn(u64, u64, u32) -> i32,
+    health_check: unsafe extern "C" fn(u64, u64, *mut DcgmHealthResponse) -> i32,
+    entities_get_latest_values:
+        unsafe extern "C" fn(u64, *mut DcgmGroupEntityPair, i32, *mut u16, i32, u32, *mut DcgmFieldValue) -> i32,
+    policy_set: unsafe extern "C" fn(u64, u64, *mut DcgmPolicy, *mut u8) -> i32,
+    policy_register: unsafe extern "C" fn(
+        u64,
+        u64,
+        i32,
+        unsafe extern "C" fn(*mut DcgmPolicyCallbackResponse),
+        unsafe extern "C" fn(*mut DcgmPolicyCallbackResponse),
+    ) -> i32,
+    policy_unregister: unsafe extern "C" fn(u64, u64, i32) -> i32,
+}
+
+impl DcgmApi {
+    fn resolve(lib: &Library) -> Result<Self> {
+        macro_rules! symbol {
+            ($name:literal) => {
+                unsafe { *lib.get::<_>($name)? }
+            };
+        }
+
+        Ok(DcgmApi {
+            init: symbol!(b"dcgmInit"),
+            start_embedded: symbol!(b"dcgmStartEmbedded"),
+            connect: symbol!(b"dcgmConnect"),
+            update_all_fields: symbol!(b"dcgmUpdateAllFields"),
+            stop_embedded: symbol!(b"dcgmStopEmbedded"),
+            shutdown: symbol!(b"dcgmShutdown"),
+            get_all_devices: symbol!(b"dcgmGetAllDevices"),
+            field_group_create: symbol!(b"dcgmFieldGroupCreate"),
+            field_group_destroy: symbol!(b"dcgmFieldGroupDestroy"),
+            watch_fields: symbol!(b"dcgmWatchFields"),
+            unwatch_fields: symbol!(b"dcgmUnwatchFields"),
+            watch_pid_fields: symbol!(b"dcgmWatchPidFields"),
+            get_all_processes: symbol!(b"dcgmGetAllProcesses"),
+            group_create: symbol!(b"dcgmGroupCreate"),
+            group_add_device: symbol!(b"dcgmGroupAddDevice"),
+            group_destroy: symbol!(b"dcgmGroupDestroy"),
+            config_set: symbol!(b"dcgmConfigSet"),
+            config_get: symbol!(b"dcgmConfigGet"),
+            health_set: symbol!(b"dcgmHealthSet"),
+            health_check: symbol!(b"dcgmHealthCheck"),
+            entities_get_latest_values: symbol!(b"dcgmEntitiesGetLatestValues"),
+            policy_set: symbol!(b"dcgmPolicySet"),
+            policy_register: symbol!(b"dcgmPolicyRegister"),
+            policy_unregister: symbol!(b"dcgmPolicyUnregister"),
+        })
+    }
+}
+
 pub struct DcgmHandle {
     handle: u64,
+    // Never read directly: `api`'s function pointers are resolved from this
+    // `Library` and only stay valid for as long as it's loaded, so it has to
+    // be kept alive alongside `api` even though nothing calls back into it.
+    #[allow(dead_code)]
     lib: Library,
-    // Track whether we've enabled watches for power and profiling metrics
-    power_watched: bool,
-    prof_watched: bool,
-    power_group_id: u64,
-    prof_group_id: u64,
+    api: DcgmApi,
+    // Keyed registry of active field-group watches, replacing the old
+    // per-feature power_watched/prof_watched booleans
+    watches: Mutex<HashMap<WatchId, WatchEntry>>,
+    next_watch_id: AtomicU64,
+    // Track whether PID accounting has been turned on for the host engine
+    accounting_watched: AtomicBool,
 }
 
 impl DcgmHandle {
     pub fn new() -> Result<Self> {
         let lib = unsafe { Library::new("libdcgm.so") }?;
+        let api = DcgmApi::resolve(&lib)?;
 
-        let dcgm_init: Symbol<unsafe extern "C" fn() -> i32> = unsafe { lib.get(b"dcgmInit")? };
-
-        let result = unsafe { dcgm_init() };
+        let result = unsafe { (api.init)() };
         if result != 0 {
             return Err(DcgmError::ApiError(result, "dcgmInit failed".to_string()));
         }
 
         // Start embedded mode
-        let dcgm_start_embedded: Symbol<unsafe extern "C" fn(i32, *mut u64) -> i32> =
-            unsafe { lib.get(b"dcgmStartEmbedded")? };
-
         let mut handle: u64 = 0;
-        let result = unsafe { dcgm_start_embedded(1, &mut handle) }; // 1 = AUTO mode
+        let result = unsafe { (api.start_embedded)(1, &mut handle) }; // 1 = AUTO mode
         if result != 0 {
             return Err(DcgmError::ApiError(
                 result,
@@ -66,10 +208,7 @@ impl DcgmHandle {
         }
 
         // Update all fields initially to make sure we're getting fresh data
-        let dcgm_update_all_fields: Symbol<unsafe extern "C" fn(u64, i32) -> i32> =
-            unsafe { lib.get(b"dcgmUpdateAllFields")? };
-
-        let result = unsafe { dcgm_update_all_fields(handle, 1) }; // Wait for update
+        let result = unsafe { (api.update_all_fields)(handle, 1) }; // Wait for update
         if result != 0 {
             eprintln!("Warning: dcgmUpdateAllFields failed with code {}", result);
         }
@@ -77,27 +216,23 @@ impl DcgmHandle {
         Ok(DcgmHandle {
             handle,
             lib,
-            power_watched: false,
-            prof_watched: false,
-            power_group_id: 0,
-            prof_group_id: 0,
+            api,
+            watches: Mutex::new(HashMap::new()),
+            next_watch_id: AtomicU64::new(1),
+            accounting_watched: AtomicBool::new(false),
         })
     }
 
     pub fn with_connection(hostname: &str, port: Option<u16>) -> Result<Self> {
         let lib = unsafe { Library::new("libdcgm.so") }?;
+        let api = DcgmApi::resolve(&lib)?;
 
-        let dcgm_init: Symbol<unsafe extern "C" fn() -> i32> = unsafe { lib.get(b"dcgmInit")? };
-
-        let result = unsafe { dcgm_init() };
+        let result = unsafe { (api.init)() };
         if result != 0 {
             return Err(DcgmError::ApiError(result, "dcgmInit failed".to_string()));
         }
 
         // Connect to remote hostengine
-        let dcgm_connect: Symbol<unsafe extern "C" fn(*const i8, *mut u64) -> i32> =
-            unsafe { lib.get(b"dcgmConnect")? };
-
         let addr_string = match port {
             Some(p) => format!("{}:{}", hostname, p),
             None => hostname.to_string(),
@@ -105,16 +240,13 @@ impl DcgmHandle {
 
         let c_addr = CString::new(addr_string).unwrap();
         let mut handle: u64 = 0;
-        let result = unsafe { dcgm_connect(c_addr.as_ptr(), &mut handle) };
+        let result = unsafe { (api.connect)(c_addr.as_ptr(), &mut handle) };
         if result != 0 {
             return Err(DcgmError::ConnectionFailed);
         }
 
         // Update all fields initially to make sure we're getting fresh data
-        let dcgm_update_all_fields: Symbol<unsafe extern "C" fn(u64, i32) -> i32> =
-            unsafe { lib.get(b"dcgmUpdateAllFields")? };
-
-        let result = unsafe { dcgm_update_all_fields(handle, 1) }; // Wait for update
+        let result = unsafe { (api.update_all_fields)(handle, 1) }; // Wait for update
         if result != 0 {
             eprintln!("Warning: dcgmUpdateAllFields failed with code {}", result);
         }
@@ -122,53 +254,36 @@ impl DcgmHandle {
         Ok(DcgmHandle {
             handle,
             lib,
-            power_watched: false,
-            prof_watched: false,
-            power_group_id: 0,
-            prof_group_id: 0,
+            api,
+            watches: Mutex::new(HashMap::new()),
+            next_watch_id: AtomicU64::new(1),
+            accounting_watched: AtomicBool::new(false),
         })
     }
 
-    // Enable watching specific metrics for all GPUs
-    pub fn enable_power_metrics(&mut self) -> Result<()> {
-        if self.power_watched {
-            return Ok(());
-        }
-
-        // Just use the field directly through dcgmEntitiesGetLatestValues
-        // This avoids the field group creation which might be causing issues
-        self.power_watched = true;
-        Ok(())
-    }
-
-    // Enable watching profiling metrics (including SM activity)
-    pub fn enable_profiling_metrics(&mut self) -> Result<()> {
-        if self.prof_watched {
-            return Ok(());
-        }
-
-        // For profiling metrics, we need to set up proper watching
-        // This likely requires root permissions
-        let dcgm_field_group_create: Symbol<
-            unsafe extern "C" fn(
-                handle: u64,
-                num_field_ids: i32,
-                field_ids: *mut u16,
-                field_group_name: *const i8,
-                field_group_id: *mut u64,
-            ) -> i32,
-        > = unsafe { self.lib.get(b"dcgmFieldGroupCreate")? };
-
-        let field_ids = [crate::dcgm_types::DCGM_FI_PROF_SM_ACTIVE];
-        let pid = std::process::id();
-        let field_group_name = CString::new(format!("ProfMetrics{}", pid)).unwrap();
+    /// Create a field group for `field_ids` and watch it on all GPUs, returning
+    /// an RAII [`WatchHandle`] that unwatches and destroys the group when
+    /// dropped. This is the shared plumbing behind `enable_power_metrics` and
+    /// `enable_profiling_metrics`; call it directly to watch any other set of
+    /// fields.
+    pub fn watch_fields(
+        self: &Arc<Self>,
+        field_ids: &[u16],
+        update_freq_usec: i64,
+        max_keep_age_sec: f64,
+        max_keep_samples: i32,
+    ) -> Result<WatchHandle> {
+        let watch_id = self.next_watch_id.fetch_add(1, Ordering::SeqCst);
+        let field_group_name =
+            CString::new(format!("Watch{}-{}", std::process::id(), watch_id)).unwrap();
+        let mut field_ids = field_ids.to_vec();
         let mut field_group_id: u64 = 0;
 
         let result = unsafe {
-            dcgm_field_group_create(
+            (self.api.field_group_create)(
                 self.handle,
                 field_ids.len() as i32,
-                field_ids.as_ptr() as *mut u16,
+                field_ids.as_mut_ptr(),
                 field_group_name.as_ptr(),
                 &mut field_group_id,
             )
@@ -179,32 +294,152 @@ impl DcgmHandle {
         {
             return Err(DcgmError::ApiError(
                 result,
-                "dcgmFieldGroupCreate failed for profiling metrics".to_string(),
+                "dcgmFieldGroupCreate failed".to_string(),
             ));
         }
 
-        self.prof_group_id = field_group_id;
+        let entity_group_id: u64 = 0x7fffffff; // DCGM_GROUP_ALL_GPUS
+
+        let result = unsafe {
+            (self.api.watch_fields)(
+                self.handle,
+                entity_group_id,
+                field_group_id,
+                update_freq_usec,
+                max_keep_age_sec,
+                max_keep_samples,
+            )
+        };
+
+        if result != 0 {
+            // Best-effort cleanup of the field group we just created.
+            let _ = unsafe { (self.api.field_group_destroy)(self.handle, field_group_id) };
+
+            return if result == -29 {
+                // DCGM_ST_REQUIRES_ROOT
+                Err(DcgmError::RequiresRoot(
+                    "Watching these fields requires root access. Try running with sudo"
+                        .to_string(),
+                ))
+            } else {
+                Err(DcgmError::ApiError(
+                    result,
+                    "dcgmWatchFields failed".to_string(),
+                ))
+            };
+        }
+
+        if let Ok(mut watches) = self.watches.lock() {
+            watches.insert(
+                watch_id,
+                WatchEntry {
+                    field_group_id,
+                    entity_group_id,
+                    update_freq_usec,
+                    max_keep_age_sec,
+                    max_keep_samples,
+                },
+            );
+        }
+
+        Ok(WatchHandle {
+            id: watch_id,
+            handle: Arc::clone(self),
+        })
+    }
+
+    /// Tear down the field group behind a [`WatchHandle`]. Called from
+    /// `WatchHandle::drop`; a no-op if the watch was already torn down.
+    fn teardown_watch(&self, id: WatchId) {
+        let entry = match self.watches.lock() {
+            Ok(mut watches) => watches.remove(&id),
+            Err(_) => None,
+        };
+
+        let Some(entry) = entry else {
+            return;
+        };
+
+        if self.handle == 0 {
+            return;
+        }
+
+        unsafe {
+            let _ = (self.api.unwatch_fields)(
+                self.handle,
+                entry.entity_group_id,
+                entry.field_group_id,
+            );
+            let _ = (self.api.field_group_destroy)(self.handle, entry.field_group_id);
+        }
+    }
 
-        // Start watching this field group on all GPUs
-        let dcgm_watch_fields: Symbol<
-            unsafe extern "C" fn(
-                handle: u64,
-                group_id: u64,
-                field_group_id: u64,
-                update_freq: i64,
-                max_keep_age: f64,
-                max_keep_samples: i32,
-            ) -> i32,
-        > = unsafe { self.lib.get(b"dcgmWatchFields")? };
+    /// Look up the retention settings `id` was created with, or `None` if
+    /// the watch has already been torn down.
+    pub fn watch_info(&self, id: WatchId) -> Option<WatchInfo> {
+        self.watches.lock().ok()?.get(&id).map(|entry| WatchInfo {
+            update_freq_usec: entry.update_freq_usec,
+            max_keep_age_sec: entry.max_keep_age_sec,
+            max_keep_samples: entry.max_keep_samples,
+        })
+    }
+
+    // Enable watching core power metrics (usage, energy, enforced limit) for all GPUs
+    pub fn enable_power_metrics(self: &Arc<Self>) -> Result<WatchHandle> {
+        let field_ids = [
+            crate::dcgm_types::DCGM_FI_DEV_POWER_USAGE,
+            crate::dcgm_types::DCGM_FI_DEV_TOTAL_ENERGY_CONSUMPTION,
+            crate::dcgm_types::DCGM_FI_DEV_ENFORCED_POWER_LIMIT,
+        ];
+        self.watch_fields(&field_ids, 1_000_000, 3600.0, 0)
+    }
+
+    // Enable watching profiling metrics (SM/Tensor/DRAM activity, PCIe & NVLink throughput)
+    //
+    // The prof fields are sampled rather than instantaneous, so the caller picks how
+    // often DCGM should refresh them (`update_freq_usec`) and how long samples are kept
+    // around (`max_keep_age_sec`) before `get_profiling_metrics` reads them back.
+    pub fn enable_profiling_metrics(
+        self: &Arc<Self>,
+        update_freq_usec: i64,
+        max_keep_age_sec: f64,
+    ) -> Result<WatchHandle> {
+        let field_ids = [
+            crate::dcgm_types::DCGM_FI_PROF_SM_ACTIVE,
+            crate::dcgm_types::DCGM_FI_PROF_SM_OCCUPANCY,
+            crate::dcgm_types::DCGM_FI_PROF_PIPE_TENSOR_ACTIVE,
+            crate::dcgm_types::DCGM_FI_PROF_PIPE_FP64_ACTIVE,
+            crate::dcgm_types::DCGM_FI_PROF_PIPE_FP32_ACTIVE,
+            crate::dcgm_types::DCGM_FI_PROF_PIPE_FP16_ACTIVE,
+            crate::dcgm_types::DCGM_FI_PROF_DRAM_ACTIVE,
+            crate::dcgm_types::DCGM_FI_PROF_PCIE_TX_BYTES,
+            crate::dcgm_types::DCGM_FI_PROF_PCIE_RX_BYTES,
+            crate::dcgm_types::DCGM_FI_PROF_NVLINK_TX_BYTES,
+            crate::dcgm_types::DCGM_FI_PROF_NVLINK_RX_BYTES,
+        ];
+        self.watch_fields(&field_ids, update_freq_usec, max_keep_age_sec, 0)
+    }
+
+    // Enable per-process accounting so we can later attribute memory/SM
+    // usage to individual PIDs via `get_process_metrics`. `update_freq_usec`
+    // and `max_keep_age_sec` control how often DCGM refreshes the accounting
+    // stats and how long it retains them, mirroring `watch_fields`.
+    pub fn enable_accounting(
+        &self,
+        update_freq_usec: i64,
+        max_keep_age_sec: f64,
+    ) -> Result<()> {
+        if self.accounting_watched.load(Ordering::SeqCst) {
+            return Ok(());
+        }
 
         let result = unsafe {
-            dcgm_watch_fields(
+            (self.api.watch_pid_fields)(
                 self.handle,
                 0x7fffffff, // DCGM_GROUP_ALL_GPUS
-                field_group_id,
-                100000, // Update every 100ms
-                0.0,    // No limit on keep age
-                0,      // No limit on keep samples
+                update_freq_usec,
+                max_keep_age_sec,
+                0,
             )
         };
 
@@ -212,27 +447,24 @@ impl DcgmHandle {
             if result == -29 {
                 // DCGM_ST_REQUIRES_ROOT
                 return Err(DcgmError::RequiresRoot(
-                    "Profiling metrics require root access. Try running with sudo".to_string(),
+                    "PID accounting requires root access. Try running with sudo".to_string(),
                 ));
             } else {
                 return Err(DcgmError::ApiError(
                     result,
-                    "dcgmWatchFields failed for profiling metrics".to_string(),
+                    "dcgmWatchPidFields failed".to_string(),
                 ));
             }
         }
 
-        self.prof_watched = true;
+        self.accounting_watched.store(true, Ordering::SeqCst);
         Ok(())
     }
 
     // Force an update of all watched fields
     pub fn update_all_fields(&self, wait_for_update: bool) -> Result<()> {
-        let dcgm_update_all_fields: Symbol<unsafe extern "C" fn(u64, i32) -> i32> =
-            unsafe { self.lib.get(b"dcgmUpdateAllFields")? };
-
         let wait_flag = if wait_for_update { 1 } else { 0 };
-        let result = unsafe { dcgm_update_all_fields(self.handle, wait_flag) };
+        let result = unsafe { (self.api.update_all_fields)(self.handle, wait_flag) };
 
         if result != 0 {
             return Err(DcgmError::ApiError(
@@ -249,13 +481,11 @@ impl DcgmHandle {
     }
 
     pub fn get_device_count(&self) -> Result<i32> {
-        let dcgm_get_all_devices: Symbol<unsafe extern "C" fn(u64, *mut u32, *mut i32) -> i32> =
-            unsafe { self.lib.get(b"dcgmGetAllDevices")? };
-
         let mut gpu_ids: [u32; 32] = [0; 32]; // DCGM_MAX_NUM_DEVICES
         let mut count: i32 = 0;
 
-        let result = unsafe { dcgm_get_all_devices(self.handle, gpu_ids.as_mut_ptr(), &mut count) };
+        let result =
+            unsafe { (self.api.get_all_devices)(self.handle, gpu_ids.as_mut_ptr(), &mut count) };
         if result != 0 {
             return Err(DcgmError::ApiError(
                 result,
@@ -267,13 +497,11 @@ impl DcgmHandle {
     }
 
     pub fn get_device_ids(&self) -> Result<Vec<u32>> {
-        let dcgm_get_all_devices: Symbol<unsafe extern "C" fn(u64, *mut u32, *mut i32) -> i32> =
-            unsafe { self.lib.get(b"dcgmGetAllDevices")? };
-
         let mut gpu_ids: [u32; 32] = [0; 32]; // DCGM_MAX_NUM_DEVICES
         let mut count: i32 = 0;
 
-        let result = unsafe { dcgm_get_all_devices(self.handle, gpu_ids.as_mut_ptr(), &mut count) };
+        let result =
+            unsafe { (self.api.get_all_devices)(self.handle, gpu_ids.as_mut_ptr(), &mut count) };
         if result != 0 {
             return Err(DcgmError::ApiError(
                 result,
@@ -283,6 +511,70 @@ impl DcgmHandle {
 
         Ok(gpu_ids[0..count as usize].to_vec())
     }
+
+    /// Read the latest values of `field_ids` for `device_id`. `live` sets
+    /// `DCGM_FV_FLAG_LIVE_DATA`, asking DCGM to bypass the watch cache and
+    /// query the driver directly instead of returning the last sampled value.
+    pub fn get_device_field_values(
+        &self,
+        device_id: u32,
+        field_ids: &[u16],
+        live: bool,
+    ) -> Result<Vec<DcgmFieldValue>> {
+        let mut entities = [DcgmGroupEntityPair {
+            entity_group_id: DCGM_FE_GPU,
+            entity_id: device_id,
+        }];
+        let mut field_ids = field_ids.to_vec();
+        let mut values: Vec<DcgmFieldValue> = field_ids.iter().map(|_| DcgmFieldValue::default()).collect();
+        let flags = if live { DCGM_FV_FLAG_LIVE_DATA } else { 0 };
+
+        let result = unsafe {
+            (self.api.entities_get_latest_values)(
+                self.handle,
+                entities.as_mut_ptr(),
+                entities.len() as i32,
+                field_ids.as_mut_ptr(),
+                field_ids.len() as i32,
+                flags,
+                values.as_mut_ptr(),
+            )
+        };
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmEntitiesGetLatestValues failed".to_string(),
+            ));
+        }
+
+        Ok(values)
+    }
+
+    /// Read `device_id`'s product name (`DCGM_FI_DEV_NAME`).
+    pub fn get_device_name(&self, device_id: u32) -> Result<String> {
+        let field_values =
+            self.get_device_field_values(device_id, &[crate::dcgm_types::DCGM_FI_DEV_NAME], true)?;
+
+        let field_value = field_values.first().ok_or_else(|| {
+            DcgmError::FieldValueError("No device name value returned".to_string())
+        })?;
+
+        if field_value.status != DCGM_ST_OK {
+            return Err(DcgmError::FieldValueError(format!(
+                "Device name not available for GPU {}",
+                device_id
+            )));
+        }
+
+        let bytes: Vec<u8> = unsafe { field_value.value.str }
+            .iter()
+            .take_while(|&&b| b != 0)
+            .map(|&b| b as u8)
+            .collect();
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
 }
 
 impl Drop for DcgmHandle {
@@ -290,51 +582,25 @@ impl Drop for DcgmHandle {
         if self.handle != 0 {
             // Try to clean up
             unsafe {
-                // First try to stop watching any fields if we created field groups
-                if self.power_group_id != 0 || self.prof_group_id != 0 {
-                    if let Ok(dcgm_unwatch_fields) =
-                        self.lib
-                            .get::<unsafe extern "C" fn(u64, u64, u64) -> i32>(b"dcgmUnwatchFields")
-                    {
-                        if self.power_group_id != 0 {
-                            let _ =
-                                dcgm_unwatch_fields(self.handle, 0x7fffffff, self.power_group_id);
-                        }
-                        if self.prof_group_id != 0 {
-                            let _ =
-                                dcgm_unwatch_fields(self.handle, 0x7fffffff, self.prof_group_id);
-                        }
-                    }
-                }
-
-                // Then destroy field groups
-                if let Ok(dcgm_field_group_destroy) = self
-                    .lib
-                    .get::<unsafe extern "C" fn(u64, u64) -> i32>(b"dcgmFieldGroupDestroy")
-                {
-                    if self.power_group_id != 0 {
-                        let _ = dcgm_field_group_destroy(self.handle, self.power_group_id);
-                    }
-                    if self.prof_group_id != 0 {
-                        let _ = dcgm_field_group_destroy(self.handle, self.prof_group_id);
+                // Tear down any field-group watches still in the registry
+                // (e.g. a WatchHandle leaked with `mem::forget`, whose Drop
+                // never ran to remove its entry).
+                if let Ok(watches) = self.watches.lock() {
+                    for entry in watches.values() {
+                        let _ = (self.api.unwatch_fields)(
+                            self.handle,
+                            entry.entity_group_id,
+                            entry.field_group_id,
+                        );
+                        let _ = (self.api.field_group_destroy)(self.handle, entry.field_group_id);
                     }
                 }
 
                 // Then stop embedded mode if we're in it
-                if let Ok(dcgm_stop_embedded) = self
-                    .lib
-                    .get::<unsafe extern "C" fn(u64) -> i32>(b"dcgmStopEmbedded")
-                {
-                    let _ = dcgm_stop_embedded(self.handle);
-                }
+                let _ = (self.api.stop_embedded)(self.handle);
 
                 // Finally shut down DCGM
-                if let Ok(dcgm_shutdown) = self
-                    .lib
-                    .get::<unsafe extern "C" fn() -> i32>(b"dcgmShutdown")
-                {
-                    let _ = dcgm_shutdown();
-                }
+                let _ = (self.api.shutdown)();
             }
         }
     }
@@ -0,0 +1,583 @@
+use crate::dcgm_types::{
+    is_fp64_blank, is_int64_blank, DcgmPidInfo, DcgmFieldValue, DCGM_CLOCKS_EVENT_REASON_CLOCKS_SETTING,
+    DCGM_CLOCKS_EVENT_REASON_GPU_IDLE, DCGM_CLOCKS_EVENT_REASON_HW_POWER_BRAKE,
+    DCGM_CLOCKS_EVENT_REASON_HW_SLOWDOWN, DCGM_CLOCKS_EVENT_REASON_HW_THERMAL,
+    DCGM_CLOCKS_EVENT_REASON_SW_POWER_CAP, DCGM_CLOCKS_EVENT_REASON_SW_THERMAL,
+    DCGM_FI_DEV_CLOCKS_EVENT_REASONS, DCGM_FI_DEV_DEFAULT_POWER_LIMIT, DCGM_FI_DEV_ENFORCED_POWER_LIMIT,
+    DCGM_FI_DEV_FAN_SPEED, DCGM_FI_DEV_FB_FREE,
+    DCGM_FI_DEV_FB_TOTAL, DCGM_FI_DEV_FB_USED, DCGM_FI_DEV_GPU_MAX_OP_TEMP, DCGM_FI_DEV_GPU_TEMP,
+    DCGM_FI_DEV_GPU_UTIL, DCGM_FI_DEV_MAX_POWER_LIMIT, DCGM_FI_DEV_MEM_CLOCK,
+    DCGM_FI_DEV_MIN_POWER_LIMIT, DCGM_FI_DEV_POWER_USAGE,
+    DCGM_FI_DEV_POWER_VIOLATION, DCGM_FI_DEV_SM_CLOCK, DCGM_FI_DEV_THERMAL_VIOLATION,
+    DCGM_FI_DEV_TOTAL_ENERGY_CONSUMPTION, DCGM_FI_PROF_DRAM_ACTIVE, DCGM_FI_PROF_NVLINK_RX_BYTES,
+    DCGM_FI_PROF_NVLINK_TX_BYTES, DCGM_FI_PROF_PCIE_RX_BYTES, DCGM_FI_PROF_PCIE_TX_BYTES,
+    DCGM_FI_PROF_PIPE_FP16_ACTIVE, DCGM_FI_PROF_PIPE_FP32_ACTIVE, DCGM_FI_PROF_PIPE_FP64_ACTIVE,
+    DCGM_FI_PROF_PIPE_TENSOR_ACTIVE, DCGM_FI_PROF_SM_ACTIVE, DCGM_FI_PROF_SM_OCCUPANCY,
+    DCGM_INT32_BLANK, DCGM_INT64_BLANK, DCGM_MAX_PID_INFO_PROCESSES, DCGM_PROCESS_TYPE_COMPUTE,
+    DCGM_PROCESS_TYPE_GRAPHICS, DCGM_ST_NOT_SUPPORTED, DCGM_ST_OK, DCGM_ST_REQUIRES_ROOT,
+};
+use crate::{DcgmError, DcgmHandle, Result};
+use std::collections::HashMap;
+
+pub mod sampler;
+
+/// Whether a field can actually be read back for a given device, so callers
+/// can tell "not supported" apart from "blank this sample".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSupport {
+    Supported,
+    Unsupported,
+    PermissionDenied,
+}
+
+fn field_support_from_status(status: i32) -> FieldSupport {
+    match status {
+        DCGM_ST_OK => FieldSupport::Supported,
+        DCGM_ST_REQUIRES_ROOT => FieldSupport::PermissionDenied,
+        DCGM_ST_NOT_SUPPORTED => FieldSupport::Unsupported,
+        _ => FieldSupport::Unsupported,
+    }
+}
+
+/// Basic GPU metrics that should be accessible without root
+#[derive(Debug, Clone)]
+pub struct GpuMetrics {
+    pub device_id: u32,
+    pub timestamp: i64,
+    // Power metrics
+    pub power_usage: Option<f64>,           // in Watts
+    pub energy_consumption: Option<i64>,    // in mJ
+    pub enforced_power_limit: Option<i64>,  // in W
+    pub min_power_limit: Option<i64>,       // in W
+    pub max_power_limit: Option<i64>,       // in W
+    pub default_power_limit: Option<i64>,   // in W
+    pub power_violation_time: Option<i64>,  // in µs
+    // Temperature metrics
+    pub gpu_temp: Option<i64>,               // in °C
+    pub max_gpu_temp: Option<i64>,           // in °C
+    pub thermal_violation_time: Option<i64>, // in µs
+    // Fan metrics
+    pub fan_speed: Option<i64>, // in %
+    // Memory metrics
+    pub fb_total: Option<i64>, // in MB
+    pub fb_free: Option<i64>,  // in MB
+    pub fb_used: Option<i64>,  // in MB
+    // Utilization metrics
+    pub gpu_util: Option<i64>, // in %
+    // Clock metrics
+    pub sm_clock: Option<i64>,  // in MHz
+    pub mem_clock: Option<i64>, // in MHz
+    // Throttling reasons (bitmask)
+    pub clock_throttle_reasons: Option<u64>,   // bitmask
+    pub throttle_reasons: Option<Vec<String>>, // human-readable reasons
+    // Field IDs that came back unsupported for this device, so callers can
+    // stop polling them and explain the gap in their UI
+    pub unsupported_fields: Vec<u16>,
+    // Field IDs that came back permission-denied (recoverable with root),
+    // kept separate from `unsupported_fields` (which never will be)
+    pub permission_denied_fields: Vec<u16>,
+}
+
+impl GpuMetrics {
+    fn new(device_id: u32) -> Self {
+        GpuMetrics {
+            device_id,
+            timestamp: 0,
+            power_usage: None,
+            energy_consumption: None,
+            enforced_power_limit: None,
+            min_power_limit: None,
+            max_power_limit: None,
+            default_power_limit: None,
+            power_violation_time: None,
+            gpu_temp: None,
+            max_gpu_temp: None,
+            thermal_violation_time: None,
+            fan_speed: None,
+            fb_total: None,
+            fb_free: None,
+            fb_used: None,
+            gpu_util: None,
+            sm_clock: None,
+            mem_clock: None,
+            clock_throttle_reasons: None,
+            throttle_reasons: None,
+            unsupported_fields: Vec::new(),
+            permission_denied_fields: Vec::new(),
+        }
+    }
+
+    fn decode_throttle_reasons(&mut self) {
+        if let Some(reasons) = self.clock_throttle_reasons {
+            let mut decoded = Vec::new();
+
+            if reasons & DCGM_CLOCKS_EVENT_REASON_GPU_IDLE != 0 {
+                decoded.push("GPU_IDLE".to_string());
+            }
+            if reasons & DCGM_CLOCKS_EVENT_REASON_CLOCKS_SETTING != 0 {
+                decoded.push("CLOCKS_SETTING".to_string());
+            }
+            if reasons & DCGM_CLOCKS_EVENT_REASON_SW_POWER_CAP != 0 {
+                decoded.push("SW_POWER_CAP".to_string());
+            }
+            if reasons & DCGM_CLOCKS_EVENT_REASON_HW_SLOWDOWN != 0 {
+                decoded.push("HW_SLOWDOWN".to_string());
+            }
+            if reasons & DCGM_CLOCKS_EVENT_REASON_SW_THERMAL != 0 {
+                decoded.push("SW_THERMAL".to_string());
+            }
+            if reasons & DCGM_CLOCKS_EVENT_REASON_HW_THERMAL != 0 {
+                decoded.push("HW_THERMAL".to_string());
+            }
+            if reasons & DCGM_CLOCKS_EVENT_REASON_HW_POWER_BRAKE != 0 {
+                decoded.push("HW_POWER_BRAKE".to_string());
+            }
+
+            self.throttle_reasons = Some(decoded);
+        }
+    }
+}
+
+impl DcgmHandle {
+    /// Get basic GPU metrics that should be accessible without root
+    pub fn get_basic_metrics(&self, device_id: u32) -> Result<GpuMetrics> {
+        // Force an update to get the latest values
+        self.update_all_fields(true)?;
+
+        // List of field IDs we want to query
+        let field_ids = [
+            DCGM_FI_DEV_POWER_USAGE,
+            DCGM_FI_DEV_TOTAL_ENERGY_CONSUMPTION,
+            DCGM_FI_DEV_GPU_TEMP,
+            DCGM_FI_DEV_GPU_MAX_OP_TEMP,
+            DCGM_FI_DEV_ENFORCED_POWER_LIMIT,
+            DCGM_FI_DEV_MIN_POWER_LIMIT,
+            DCGM_FI_DEV_MAX_POWER_LIMIT,
+            DCGM_FI_DEV_DEFAULT_POWER_LIMIT,
+            DCGM_FI_DEV_FAN_SPEED,
+            DCGM_FI_DEV_FB_TOTAL,
+            DCGM_FI_DEV_FB_FREE,
+            DCGM_FI_DEV_FB_USED,
+            DCGM_FI_DEV_GPU_UTIL,
+            DCGM_FI_DEV_SM_CLOCK,
+            DCGM_FI_DEV_MEM_CLOCK,
+            DCGM_FI_DEV_POWER_VIOLATION,
+            DCGM_FI_DEV_THERMAL_VIOLATION,
+            DCGM_FI_DEV_CLOCKS_EVENT_REASONS,
+        ];
+
+        // Get the field values with the LIVE flag to ensure we get the current data
+        let field_values = self.get_device_field_values(device_id, &field_ids, true)?;
+
+        if field_values.is_empty() {
+            return Err(DcgmError::FieldValueError(
+                "No metrics data returned".to_string(),
+            ));
+        }
+
+        // Create a metrics object to hold the results
+        let mut metrics = GpuMetrics::new(device_id);
+
+        // The latest timestamp we find - use as overall timestamp
+        let mut latest_timestamp = 0;
+
+        // Process the field values
+        for field_value in field_values {
+            // Update the latest timestamp
+            if field_value.timestamp > latest_timestamp {
+                latest_timestamp = field_value.timestamp;
+            }
+
+            match field_support_from_status(field_value.status) {
+                FieldSupport::Supported => {}
+                FieldSupport::PermissionDenied => {
+                    metrics.permission_denied_fields.push(field_value.field_id);
+                    continue;
+                }
+                FieldSupport::Unsupported => {
+                    metrics.unsupported_fields.push(field_value.field_id);
+                    continue;
+                }
+            }
+
+            match field_value.field_id {
+                DCGM_FI_DEV_POWER_USAGE => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.power_usage = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_TOTAL_ENERGY_CONSUMPTION => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.energy_consumption = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_GPU_TEMP => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.gpu_temp = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_GPU_MAX_OP_TEMP => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.max_gpu_temp = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_ENFORCED_POWER_LIMIT => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.enforced_power_limit = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_MIN_POWER_LIMIT => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.min_power_limit = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_MAX_POWER_LIMIT => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.max_power_limit = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_DEFAULT_POWER_LIMIT => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.default_power_limit = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_FAN_SPEED => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.fan_speed = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_FB_TOTAL => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.fb_total = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_FB_FREE => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.fb_free = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_FB_USED => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.fb_used = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_GPU_UTIL => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.gpu_util = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_SM_CLOCK => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.sm_clock = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_MEM_CLOCK => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.mem_clock = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_POWER_VIOLATION => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.power_violation_time = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_THERMAL_VIOLATION => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.thermal_violation_time = Some(value);
+                    }
+                }
+                DCGM_FI_DEV_CLOCKS_EVENT_REASONS => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.clock_throttle_reasons = Some(value as u64);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        metrics.timestamp = latest_timestamp;
+
+        // Decode throttle reasons
+        metrics.decode_throttle_reasons();
+
+        Ok(metrics)
+    }
+
+    /// Check which of `field_ids` are actually supported on `device_id`, so
+    /// callers can stop polling dead fields on mixed fleets or virtualized GPUs.
+    pub fn get_field_support(
+        &self,
+        device_id: u32,
+        field_ids: &[u16],
+    ) -> Result<HashMap<u16, FieldSupport>> {
+        self.update_all_fields(true)?;
+
+        let field_values = self.get_device_field_values(device_id, field_ids, true)?;
+
+        Ok(field_values
+            .into_iter()
+            .map(|field_value| (field_value.field_id, field_support_from_status(field_value.status)))
+            .collect())
+    }
+}
+
+/// What kind of context a process is using the GPU through, as reported by
+/// DCGM's PID accounting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessType {
+    Compute,
+    Graphics,
+    Unknown,
+}
+
+impl From<i32> for ProcessType {
+    fn from(value: i32) -> Self {
+        match value {
+            DCGM_PROCESS_TYPE_COMPUTE => ProcessType::Compute,
+            DCGM_PROCESS_TYPE_GRAPHICS => ProcessType::Graphics,
+            _ => ProcessType::Unknown,
+        }
+    }
+}
+
+/// Per-process GPU usage, attributing memory and compute to a single PID
+/// instead of the whole device.
+#[derive(Debug, Clone)]
+pub struct GpuProcessMetrics {
+    pub device_id: u32,
+    pub pid: u32,
+    pub process_type: ProcessType,
+    pub used_fb_bytes: Option<u64>, // in bytes
+    pub sm_util: Option<u32>,       // in %
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>, // None while the process is still running
+}
+
+impl DcgmHandle {
+    /// Get per-process GPU usage for `device_id`, requires
+    /// [`DcgmHandle::enable_accounting`] to have been called first.
+    pub fn get_process_metrics(&self, device_id: u32) -> Result<Vec<GpuProcessMetrics>> {
+        // Force an update so accounting data reflects currently running processes
+        self.update_all_fields(true)?;
+
+        let mut pid_infos: Vec<DcgmPidInfo> = (0..DCGM_MAX_PID_INFO_PROCESSES)
+            .map(|_| DcgmPidInfo::default())
+            .collect();
+        let mut count: u32 = 0;
+
+        let result = unsafe {
+            (self.api.get_all_processes)(
+                self.handle,
+                device_id,
+                pid_infos.as_mut_ptr(),
+                pid_infos.len() as u32,
+                &mut count,
+            )
+        };
+
+        if result != 0 {
+            return Err(DcgmError::ApiError(
+                result,
+                "dcgmGetAllProcesses failed".to_string(),
+            ));
+        }
+
+        let processes = pid_infos[..count as usize]
+            .iter()
+            .map(|info| GpuProcessMetrics {
+                device_id,
+                pid: info.pid,
+                process_type: ProcessType::from(info.process_type),
+                used_fb_bytes: if info.used_fb_bytes >= DCGM_INT64_BLANK as u64 {
+                    None
+                } else {
+                    Some(info.used_fb_bytes)
+                },
+                sm_util: if info.sm_util >= DCGM_INT32_BLANK as u32 {
+                    None
+                } else {
+                    Some(info.sm_util)
+                },
+                start_time: Some(info.start_time),
+                end_time: if info.end_time == 0 {
+                    None
+                } else {
+                    Some(info.end_time)
+                },
+            })
+            .collect();
+
+        Ok(processes)
+    }
+}
+
+/// Profiling ("roofline") metrics sampled by DCGM's prof field group, which
+/// expose saturation signals that the coarse `GPU_UTIL` field cannot.
+#[derive(Debug, Clone)]
+pub struct GpuProfilingMetrics {
+    pub device_id: u32,
+    pub timestamp: i64,
+    pub sm_active: Option<f64>,       // ratio [0, 1]
+    pub sm_occupancy: Option<f64>,    // ratio [0, 1]
+    pub tensor_active: Option<f64>,   // ratio [0, 1]
+    pub fp64_active: Option<f64>,     // ratio [0, 1]
+    pub fp32_active: Option<f64>,     // ratio [0, 1]
+    pub fp16_active: Option<f64>,     // ratio [0, 1]
+    pub dram_active: Option<f64>,     // ratio [0, 1]
+    pub pcie_tx_bytes: Option<i64>,   // bytes/sec
+    pub pcie_rx_bytes: Option<i64>,   // bytes/sec
+    pub nvlink_tx_bytes: Option<i64>, // bytes/sec
+    pub nvlink_rx_bytes: Option<i64>, // bytes/sec
+}
+
+impl GpuProfilingMetrics {
+    fn new(device_id: u32) -> Self {
+        GpuProfilingMetrics {
+            device_id,
+            timestamp: 0,
+            sm_active: None,
+            sm_occupancy: None,
+            tensor_active: None,
+            fp64_active: None,
+            fp32_active: None,
+            fp16_active: None,
+            dram_active: None,
+            pcie_tx_bytes: None,
+            pcie_rx_bytes: None,
+            nvlink_tx_bytes: None,
+            nvlink_rx_bytes: None,
+        }
+    }
+}
+
+impl DcgmHandle {
+    /// Get profiling metrics for `device_id`, requires
+    /// [`DcgmHandle::enable_profiling_metrics`] to have been called first so
+    /// the prof field group is actually being watched.
+    pub fn get_profiling_metrics(&self, device_id: u32) -> Result<GpuProfilingMetrics> {
+        self.update_all_fields(true)?;
+
+        let field_ids = [
+            DCGM_FI_PROF_SM_ACTIVE,
+            DCGM_FI_PROF_SM_OCCUPANCY,
+            DCGM_FI_PROF_PIPE_TENSOR_ACTIVE,
+            DCGM_FI_PROF_PIPE_FP64_ACTIVE,
+            DCGM_FI_PROF_PIPE_FP32_ACTIVE,
+            DCGM_FI_PROF_PIPE_FP16_ACTIVE,
+            DCGM_FI_PROF_DRAM_ACTIVE,
+            DCGM_FI_PROF_PCIE_TX_BYTES,
+            DCGM_FI_PROF_PCIE_RX_BYTES,
+            DCGM_FI_PROF_NVLINK_TX_BYTES,
+            DCGM_FI_PROF_NVLINK_RX_BYTES,
+        ];
+
+        let field_values = self.get_device_field_values(device_id, &field_ids, true)?;
+
+        if field_values.is_empty() {
+            return Err(DcgmError::FieldValueError(
+                "No profiling metrics data returned".to_string(),
+            ));
+        }
+
+        let mut metrics = GpuProfilingMetrics::new(device_id);
+        let mut latest_timestamp = 0;
+
+        for field_value in field_values {
+            if field_value.timestamp > latest_timestamp {
+                latest_timestamp = field_value.timestamp;
+            }
+
+            match field_value.field_id {
+                DCGM_FI_PROF_SM_ACTIVE => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.sm_active = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_SM_OCCUPANCY => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.sm_occupancy = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_PIPE_TENSOR_ACTIVE => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.tensor_active = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_PIPE_FP64_ACTIVE => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.fp64_active = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_PIPE_FP32_ACTIVE => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.fp32_active = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_PIPE_FP16_ACTIVE => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.fp16_active = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_DRAM_ACTIVE => {
+                    let value = unsafe { field_value.value.dbl };
+                    if !is_fp64_blank(value) {
+                        metrics.dram_active = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_PCIE_TX_BYTES => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.pcie_tx_bytes = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_PCIE_RX_BYTES => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.pcie_rx_bytes = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_NVLINK_TX_BYTES => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.nvlink_tx_bytes = Some(value);
+                    }
+                }
+                DCGM_FI_PROF_NVLINK_RX_BYTES => {
+                    let value = unsafe { field_value.value.i64 };
+                    if !is_int64_blank(value) {
+                        metrics.nvlink_rx_bytes = Some(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        metrics.timestamp = latest_timestamp;
+
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,172 @@
+// Background periodic sampler: polls a fixed set of fields across all GPUs
+// at a configurable interval and keeps a rolling window of readings per
+// (gpu, field), so callers get time-series aggregates without having to
+// build their own polling loop on top of `DcgmHandle::update_all_fields`.
+use crate::dcgm_types::decode_numeric_value;
+use crate::DcgmHandle;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Running min/max/mean/last over a (gpu, field)'s retained sampling window.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldAggregate {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub last: f64,
+    pub count: usize,
+}
+
+// Fixed-capacity ring buffer that keeps min/max/sum updated incrementally on
+// insert, and only rescans the window on eviction if the evicted sample was
+// the current extreme.
+struct RingBuffer {
+    capacity: usize,
+    samples: VecDeque<f64>,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl RingBuffer {
+    fn new(capacity: usize) -> Self {
+        RingBuffer {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.samples.push_back(value);
+        self.sum += value;
+        if value < self.min {
+            self.min = value;
+        }
+        if value > self.max {
+            self.max = value;
+        }
+
+        if self.samples.len() > self.capacity {
+            let evicted = self.samples.pop_front().unwrap();
+            self.sum -= evicted;
+
+            if evicted == self.min || evicted == self.max {
+                self.min = self.samples.iter().cloned().fold(f64::INFINITY, f64::min);
+                self.max = self
+                    .samples
+                    .iter()
+                    .cloned()
+                    .fold(f64::NEG_INFINITY, f64::max);
+            }
+        }
+    }
+
+    fn aggregate(&self) -> FieldAggregate {
+        let count = self.samples.len();
+        if count == 0 {
+            return FieldAggregate::default();
+        }
+        FieldAggregate {
+            min: self.min,
+            max: self.max,
+            mean: self.sum / count as f64,
+            last: *self.samples.back().unwrap(),
+            count,
+        }
+    }
+}
+
+/// A background thread that periodically samples a fixed set of fields
+/// across all GPUs and keeps a rolling window of aggregates per (gpu, field).
+pub struct Sampler {
+    capacity: usize,
+    aggregates: Arc<RwLock<HashMap<(u32, u16), FieldAggregate>>>,
+    stop_flag: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Sampler {
+    /// Create a sampler that retains up to `capacity` samples per (gpu, field).
+    pub fn new(capacity: usize) -> Self {
+        Sampler {
+            capacity,
+            aggregates: Arc::new(RwLock::new(HashMap::new())),
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        }
+    }
+
+    /// Start polling `field_ids` across all of `handle`'s GPUs every `interval`.
+    /// No-op if already running.
+    pub fn start(&mut self, handle: Arc<DcgmHandle>, interval: Duration, field_ids: Vec<u16>) {
+        if self.thread.is_some() {
+            return;
+        }
+
+        self.stop_flag.store(false, Ordering::SeqCst);
+        let stop_flag = Arc::clone(&self.stop_flag);
+        let aggregates = Arc::clone(&self.aggregates);
+        let capacity = self.capacity;
+
+        self.thread = Some(thread::spawn(move || {
+            let mut buffers: HashMap<(u32, u16), RingBuffer> = HashMap::new();
+
+            while !stop_flag.load(Ordering::SeqCst) {
+                if handle.update_all_fields(true).is_ok() {
+                    if let Ok(device_ids) = handle.get_device_ids() {
+                        for gpu_id in device_ids {
+                            if let Ok(field_values) =
+                                handle.get_device_field_values(gpu_id, &field_ids, true)
+                            {
+                                for field_value in field_values {
+                                    if let Some(value) = decode_numeric_value(&field_value) {
+                                        buffers
+                                            .entry((gpu_id, field_value.field_id))
+                                            .or_insert_with(|| RingBuffer::new(capacity))
+                                            .push(value);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Ok(mut snapshot) = aggregates.write() {
+                    for (key, buffer) in &buffers {
+                        snapshot.insert(*key, buffer.aggregate());
+                    }
+                }
+
+                thread::sleep(interval);
+            }
+        }));
+    }
+
+    /// Stop the background thread and join it, if running.
+    pub fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    /// Cheaply clone the current aggregates; never blocks the sampling thread.
+    pub fn snapshot(&self) -> HashMap<(u32, u16), FieldAggregate> {
+        self.aggregates
+            .read()
+            .map(|aggregates| aggregates.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,266 @@
+// Push-style subscription layer on top of `DcgmHandle`: a single background
+// poller fans out field readings to any number of independent subscribers,
+// each with its own interval and backpressure policy, instead of every
+// consumer building its own polling loop around the synchronous getters.
+use crate::dcgm_types::decode_numeric_value;
+use crate::DcgmHandle;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// A single timestamped field reading delivered to a subscriber.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSample {
+    pub gpu_id: u32,
+    pub field_id: u16,
+    pub value: f64,
+    pub timestamp: i64,
+}
+
+/// What to do with a subscriber's queue when it can't keep up with the poller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+    /// Evict the oldest queued sample to make room for the newest one.
+    DropOldest,
+    /// Block the poller until the subscriber drains its queue.
+    ///
+    /// Since one poller thread fans out to every subscriber, a blocked
+    /// subscriber under this policy delays delivery to all the others too.
+    Block,
+}
+
+struct Subscriber {
+    id: u64,
+    field_ids: Vec<u16>,
+    gpu_ids: Vec<u32>,
+    interval: Duration,
+    last_polled: Mutex<Option<Instant>>,
+    policy: Backpressure,
+    // Staging buffer the poller pushes into (and bounds under DropOldest)
+    // before handing samples off to the subscriber's channel.
+    pending: Mutex<VecDeque<FieldSample>>,
+    capacity: usize,
+    sender: SyncSender<FieldSample>,
+}
+
+/// A handle to a live subscription. Dropping it unregisters the subscriber
+/// from the hub; once the last subscriber for a field drops, the hub stops
+/// bothering to poll it.
+pub struct Subscription {
+    id: u64,
+    hub: Arc<SubscriptionHubInner>,
+    pub receiver: Receiver<FieldSample>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.hub.unsubscribe(self.id);
+    }
+}
+
+struct SubscriptionHubInner {
+    subscribers: Mutex<Vec<Arc<Subscriber>>>,
+}
+
+impl SubscriptionHubInner {
+    fn unsubscribe(&self, id: u64) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|subscriber| subscriber.id != id);
+        }
+    }
+}
+
+/// Owns the single polling thread that multiplexes every active
+/// [`Subscription`] on top of one `DcgmHandle`.
+pub struct SubscriptionHub {
+    handle: Arc<DcgmHandle>,
+    inner: Arc<SubscriptionHubInner>,
+    next_id: AtomicU64,
+    tick: Duration,
+    stop_flag: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SubscriptionHub {
+    /// Create a hub that polls at most once per `tick` — the finest interval
+    /// any subscription can ask for.
+    pub fn new(handle: Arc<DcgmHandle>, tick: Duration) -> Self {
+        SubscriptionHub {
+            handle,
+            inner: Arc::new(SubscriptionHubInner {
+                subscribers: Mutex::new(Vec::new()),
+            }),
+            next_id: AtomicU64::new(1),
+            tick,
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        }
+    }
+
+    /// Subscribe to `field_ids` on `gpu_ids`, polled every `interval`, with
+    /// up to `queue_capacity` samples buffered under `policy` before
+    /// delivery. Starts the shared poller thread on first use.
+    pub fn subscribe(
+        &mut self,
+        field_ids: Vec<u16>,
+        gpu_ids: Vec<u32>,
+        interval: Duration,
+        queue_capacity: usize,
+        policy: Backpressure,
+    ) -> Subscription {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = mpsc::sync_channel(queue_capacity.max(1));
+
+        let subscriber = Arc::new(Subscriber {
+            id,
+            field_ids,
+            gpu_ids,
+            interval,
+            last_polled: Mutex::new(None),
+            policy,
+            pending: Mutex::new(VecDeque::with_capacity(queue_capacity)),
+            capacity: queue_capacity,
+            sender,
+        });
+
+        if let Ok(mut subscribers) = self.inner.subscribers.lock() {
+            subscribers.push(subscriber);
+        }
+
+        self.ensure_poller_started();
+
+        Subscription {
+            id,
+            hub: Arc::clone(&self.inner),
+            receiver,
+        }
+    }
+
+    fn ensure_poller_started(&mut self) {
+        if let Some(thread) = &self.thread {
+            if !thread.is_finished() {
+                return;
+            }
+        }
+
+        // The previous poller thread ran dry (no subscribers left) and
+        // returned on its own; join it before starting its replacement.
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+
+        self.stop_flag.store(false, Ordering::SeqCst);
+        let handle = Arc::clone(&self.handle);
+        let inner = Arc::clone(&self.inner);
+        let stop_flag = Arc::clone(&self.stop_flag);
+        let tick = self.tick;
+
+        self.thread = Some(thread::spawn(move || {
+            while !stop_flag.load(Ordering::SeqCst) {
+                let subscribers: Vec<Arc<Subscriber>> = match inner.subscribers.lock() {
+                    Ok(subscribers) => subscribers.clone(),
+                    Err(_) => Vec::new(),
+                };
+
+                // Stop polling altogether once every subscriber has dropped.
+                if subscribers.is_empty() {
+                    break;
+                }
+
+                let now = Instant::now();
+                let due: Vec<&Arc<Subscriber>> = subscribers
+                    .iter()
+                    .filter(|subscriber| is_due(subscriber, now))
+                    .collect();
+
+                if !due.is_empty() && handle.update_all_fields(true).is_ok() {
+                    for subscriber in due {
+                        poll_subscriber(&handle, subscriber);
+                        if let Ok(mut last_polled) = subscriber.last_polled.lock() {
+                            *last_polled = Some(now);
+                        }
+                    }
+                }
+
+                thread::sleep(tick);
+            }
+        }));
+    }
+
+    /// Stop the shared poller thread; existing [`Subscription`]s simply stop
+    /// receiving new samples.
+    pub fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for SubscriptionHub {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn is_due(subscriber: &Subscriber, now: Instant) -> bool {
+    match subscriber.last_polled.lock() {
+        Ok(last_polled) => match *last_polled {
+            Some(last) => now.duration_since(last) >= subscriber.interval,
+            None => true,
+        },
+        Err(_) => true,
+    }
+}
+
+fn poll_subscriber(handle: &DcgmHandle, subscriber: &Subscriber) {
+    for &gpu_id in &subscriber.gpu_ids {
+        let field_values = match handle.get_device_field_values(gpu_id, &subscriber.field_ids, true)
+        {
+            Ok(field_values) => field_values,
+            Err(_) => continue,
+        };
+
+        for field_value in field_values {
+            let Some(value) = decode_numeric_value(&field_value) else {
+                continue;
+            };
+
+            let sample = FieldSample {
+                gpu_id,
+                field_id: field_value.field_id,
+                value,
+                timestamp: field_value.timestamp,
+            };
+
+            deliver(subscriber, sample);
+        }
+    }
+}
+
+fn deliver(subscriber: &Subscriber, sample: FieldSample) {
+    match subscriber.policy {
+        Backpressure::Block => {
+            let _ = subscriber.sender.send(sample);
+        }
+        Backpressure::DropOldest => {
+            // Stage in our own bounded buffer so we can evict the oldest
+            // sample instead of blocking when the channel is full.
+            if let Ok(mut pending) = subscriber.pending.lock() {
+                if pending.len() >= subscriber.capacity {
+                    pending.pop_front();
+                }
+                pending.push_back(sample);
+
+                while let Some(next) = pending.pop_front() {
+                    if subscriber.sender.try_send(next).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}